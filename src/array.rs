@@ -0,0 +1,165 @@
+use core::array;
+use core::mem;
+
+use crate::SparseSlots;
+
+/// A fixed-capacity, stack-allocated sibling of [`ResizingVec`](crate::ResizingVec)
+/// that needs no allocator: storage is `[Option<T>; N]` rather than a
+/// `Vec<Option<T>>`, and this module only uses `core`.
+///
+/// With the default `std` feature disabled, the crate builds under
+/// `#![no_std]` (see the `#![cfg_attr(not(feature = "std"), no_std)]` on the
+/// crate root) and `ArrayResizingVec` is usable on its own. `ResizingVec`
+/// still works too, since it only needs `alloc`, not `std` itself; enabling
+/// `serde`/`rayon` support pulls those crates' `std` assumptions back in.
+///
+/// It exposes the same sparse-slot ergonomics (`get`/`get_mut`/`remove`/`filled`/`iter`),
+/// with all but `iter` also available through the shared [`SparseSlots`]
+/// trait, but never grows past `N`:
+/// [`insert()`](#method.insert) returns `Err(t)` instead of reserving more space.
+#[derive(Debug, Clone)]
+pub struct ArrayResizingVec<T, const N: usize> {
+    data: [Option<T>; N],
+    active: usize,
+}
+
+impl<T, const N: usize> Default for ArrayResizingVec<T, N> {
+    fn default() -> Self {
+        Self {
+            data: array::from_fn(|_| None),
+            active: 0,
+        }
+    }
+}
+
+impl<T, const N: usize> ArrayResizingVec<T, N> {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the fixed capacity of the vector, i.e. `N`.
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Returns the amount of active values.
+    #[must_use]
+    pub fn filled(&self) -> usize {
+        self.active
+    }
+
+    /// Returns the element at the given index.
+    #[must_use]
+    pub fn get(&self, idx: usize) -> Option<&T> {
+        match self.data.get(idx) {
+            Some(inner) => inner.as_ref(),
+            None => None,
+        }
+    }
+
+    /// Returns a mutable reference to element at the given index
+    #[must_use]
+    pub fn get_mut(&mut self, idx: usize) -> Option<&mut T> {
+        match self.data.get_mut(idx) {
+            Some(inner) => inner.as_mut(),
+            None => None,
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (usize, &T)> + '_ {
+        self.data
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, t)| t.as_ref().map(|e| (idx, e)))
+    }
+
+    /// Removes the element at the given index and returns the
+    /// remove element. If the given index is out of bounds
+    /// than None is being returned
+    pub fn remove(&mut self, idx: usize) -> Option<T> {
+        if let Some(slot) = self.data.get_mut(idx) {
+            let prev = slot.take();
+            if prev.is_some() {
+                self.active -= 1;
+            }
+
+            prev
+        } else {
+            None
+        }
+    }
+
+    /// Inserts the element at the given index, returning the previous value.
+    ///
+    /// Unlike [`ResizingVec::insert`](crate::ResizingVec::insert), this
+    /// never grows the backing storage: if `idx >= N` the element is handed
+    /// back in `Err` instead.
+    pub fn insert(&mut self, idx: usize, t: T) -> Result<Option<T>, T> {
+        match self.data.get_mut(idx) {
+            Some(slot) => {
+                let prev = mem::replace(slot, Some(t));
+
+                if prev.is_none() {
+                    self.active += 1;
+                }
+
+                Ok(prev)
+            }
+            None => Err(t),
+        }
+    }
+
+    /// Clears the vector, removing all values
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+}
+
+impl<T, const N: usize> SparseSlots<T> for ArrayResizingVec<T, N> {
+    fn get(&self, idx: usize) -> Option<&T> {
+        self.get(idx)
+    }
+
+    fn get_mut(&mut self, idx: usize) -> Option<&mut T> {
+        self.get_mut(idx)
+    }
+
+    fn remove(&mut self, idx: usize) -> Option<T> {
+        self.remove(idx)
+    }
+
+    fn filled(&self) -> usize {
+        self.filled()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn insert_get_remove() {
+        let mut av: ArrayResizingVec<&str, 4> = ArrayResizingVec::new();
+        assert_eq!(av.capacity(), 4);
+        assert_eq!(None, av.get(0));
+
+        assert_eq!(Ok(None), av.insert(0, "0"));
+        assert_eq!(Some(&"0"), av.get(0));
+        assert_eq!(1, av.filled());
+
+        assert_eq!(Ok(Some("0")), av.insert(0, "0v2"));
+        assert_eq!(1, av.filled());
+
+        assert_eq!(av.insert(4, "oob"), Err("oob"));
+
+        assert_eq!(Some("0v2"), av.remove(0));
+        assert_eq!(0, av.filled());
+
+        av.insert(1, "1").unwrap();
+        av.clear();
+        assert_eq!(0, av.filled());
+        assert_eq!(None, av.get(1));
+    }
+}
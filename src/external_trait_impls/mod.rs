@@ -0,0 +1,5 @@
+#[cfg(feature = "serde")]
+pub(crate) mod serde;
+
+#[cfg(feature = "rayon")]
+pub(crate) mod rayon;
@@ -0,0 +1,62 @@
+use rayon::prelude::*;
+
+use crate::ResizingVec;
+
+impl<T: Sync> ResizingVec<T> {
+    /// Returns a data-parallel iterator over the active elements,
+    /// alongside their index.
+    pub fn par_iter(&self) -> impl ParallelIterator<Item = (usize, &T)> {
+        self.data
+            .par_iter()
+            .enumerate()
+            .filter_map(|(idx, t)| t.as_ref().map(|e| (idx, e)))
+    }
+}
+
+impl<T: Send> ResizingVec<T> {
+    /// Returns a data-parallel iterator over mutable references to the
+    /// active elements, alongside their index.
+    pub fn par_iter_mut(&mut self) -> impl ParallelIterator<Item = (usize, &mut T)> {
+        self.data
+            .par_iter_mut()
+            .enumerate()
+            .filter_map(|(idx, t)| t.as_mut().map(|e| (idx, e)))
+    }
+
+    /// Consumes the vector, returning a data-parallel iterator over the
+    /// active elements, alongside their index.
+    pub fn into_par_iter(self) -> impl ParallelIterator<Item = (usize, T)> {
+        self.data
+            .into_par_iter()
+            .enumerate()
+            .filter_map(|(idx, t)| t.map(|e| (idx, e)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn par_iter_and_into_par_iter() {
+        let mut rv = ResizingVec::default();
+        rv.insert(1, 10);
+        rv.insert(3, 30);
+        rv.insert(5, 50);
+
+        let mut seen = rv.par_iter().collect::<Vec<_>>();
+        seen.sort_by_key(|(idx, _)| *idx);
+        assert_eq!(seen, vec![(1, &10), (3, &30), (5, &50)]);
+
+        let mut seen_mut = rv.par_iter_mut().collect::<Vec<_>>();
+        seen_mut.sort_by_key(|(idx, _)| *idx);
+        for (_, v) in seen_mut {
+            *v += 1;
+        }
+        assert_eq!(rv.get(1), Some(&11));
+
+        let mut owned = rv.into_par_iter().collect::<Vec<_>>();
+        owned.sort_by_key(|(idx, _)| *idx);
+        assert_eq!(owned, vec![(1, 11), (3, 31), (5, 51)]);
+    }
+}
@@ -0,0 +1,98 @@
+use std::collections::HashSet;
+use std::fmt;
+use std::iter::repeat_with;
+use std::marker::PhantomData;
+
+use serde::de::{Deserialize, Deserializer, Error as _, SeqAccess, Visitor};
+use serde::ser::{Serialize, SerializeSeq, Serializer};
+
+use crate::{ResizingVec, ShrinkPolicy};
+
+/// Caps how many slots (holes and elements together) a deserialized payload
+/// may reserve per element it actually carries, so a single `(huge_idx, T)` pair can't force a
+/// multi-exabyte `Vec<Option<T>>` allocation before any validation of the
+/// payload's shape has a chance to run.
+const MAX_HOLES_PER_ELEMENT: usize = 1024;
+
+/// Serializes as a sparse sequence of `(usize, T)` pairs, one per active
+/// slot, rather than as a dense sequence padded with nulls for the holes.
+impl<T: Serialize> Serialize for ResizingVec<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(Some(self.filled()))?;
+        for pair in self.iter() {
+            seq.serialize_element(&pair)?;
+        }
+        seq.end()
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for ResizingVec<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_seq(ResizingVecVisitor(PhantomData))
+    }
+}
+
+struct ResizingVecVisitor<T>(PhantomData<T>);
+
+impl<'de, T: Deserialize<'de>> Visitor<'de> for ResizingVecVisitor<T> {
+    type Value = ResizingVec<T>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a sequence of (usize, T) pairs")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut pairs: Vec<(usize, T)> = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+        while let Some(pair) = seq.next_element()? {
+            pairs.push(pair);
+        }
+
+        let mut seen = HashSet::with_capacity(pairs.len());
+        for (idx, _) in &pairs {
+            if !seen.insert(*idx) {
+                return Err(A::Error::custom(format!(
+                    "duplicate index {idx} in ResizingVec sequence"
+                )));
+            }
+        }
+
+        let max_idx = pairs.iter().map(|(idx, _)| *idx).max();
+        let len = match max_idx {
+            Some(max_idx) => {
+                let len = max_idx.checked_add(1).ok_or_else(|| {
+                    A::Error::custom("index overflows usize in ResizingVec sequence")
+                })?;
+
+                let max_allowed = pairs
+                    .len()
+                    .saturating_mul(MAX_HOLES_PER_ELEMENT)
+                    .max(MAX_HOLES_PER_ELEMENT);
+                if len > max_allowed {
+                    return Err(A::Error::custom(format!(
+                        "index {max_idx} would reserve {len} slots for only {} elements, \
+                         exceeding the sparsity limit of {MAX_HOLES_PER_ELEMENT} slots per element",
+                        pairs.len()
+                    )));
+                }
+
+                len
+            }
+            None => 0,
+        };
+        let mut data = repeat_with(|| None).take(len).collect::<Vec<_>>();
+        let active = pairs.len();
+
+        for (idx, t) in pairs {
+            data[idx] = Some(t);
+        }
+
+        Ok(ResizingVec {
+            data,
+            active,
+            shrink_policy: ShrinkPolicy::default(),
+        })
+    }
+}
@@ -1,23 +1,90 @@
 #![doc = include_str!("../README.md")]
+#![cfg_attr(not(feature = "std"), no_std)]
 
-use std::{
+extern crate alloc;
+
+use alloc::vec::Vec;
+use core::{
     iter::repeat_with,
     ops::{Index, IndexMut},
 };
 
+// `serde`/`rayon` support pulls in their respective crates, which assume a
+// `std` environment; enable the `std` feature alongside either of these to
+// build outside of a pure no_std target.
+#[cfg(any(feature = "serde", feature = "rayon"))]
+mod external_trait_impls;
+
+mod array;
+
+pub use array::ArrayResizingVec;
+
+/// Shared accessor surface between [`ResizingVec`] and its allocation-free,
+/// fixed-capacity sibling [`ArrayResizingVec`].
+pub trait SparseSlots<T> {
+    /// Returns the element at the given index.
+    fn get(&self, idx: usize) -> Option<&T>;
+
+    /// Returns a mutable reference to the element at the given index.
+    fn get_mut(&mut self, idx: usize) -> Option<&mut T>;
+
+    /// Removes the element at the given index and returns it. If the given
+    /// index is out of bounds then `None` is returned.
+    fn remove(&mut self, idx: usize) -> Option<T>;
+
+    /// Returns the amount of active values.
+    fn filled(&self) -> usize;
+}
+
 #[derive(Debug, Clone)]
 pub struct ResizingVec<T> {
     data: Vec<Option<T>>,
     /// The amount of positions in `vec`
     /// that have active (Some(_) values)
     active: usize,
+    shrink_policy: ShrinkPolicy,
 }
 
 impl<T> From<Vec<T>> for ResizingVec<T> {
     fn from(value: Vec<T>) -> Self {
         let data = value.into_iter().map(|e| Some(e)).collect::<Vec<_>>();
         let active = data.len();
-        Self { data, active }
+        Self {
+            data,
+            active,
+            shrink_policy: ShrinkPolicy::default(),
+        }
+    }
+}
+
+impl<T> IntoIterator for ResizingVec<T> {
+    type Item = (usize, T);
+    type IntoIter = core::iter::FilterMap<
+        core::iter::Enumerate<alloc::vec::IntoIter<Option<T>>>,
+        fn((usize, Option<T>)) -> Option<(usize, T)>,
+    >;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.data
+            .into_iter()
+            .enumerate()
+            .filter_map(|(idx, t)| t.map(|e| (idx, e)))
+    }
+}
+
+impl<T> FromIterator<(usize, T)> for ResizingVec<T> {
+    fn from_iter<I: IntoIterator<Item = (usize, T)>>(iter: I) -> Self {
+        let mut rv = Self::default();
+        rv.extend(iter);
+        rv
+    }
+}
+
+impl<T> Extend<(usize, T)> for ResizingVec<T> {
+    fn extend<I: IntoIterator<Item = (usize, T)>>(&mut self, iter: I) {
+        for (idx, t) in iter {
+            self.insert(idx, t);
+        }
     }
 }
 
@@ -40,6 +107,7 @@ impl<T> Default for ResizingVec<T> {
         Self {
             data: Vec::default(),
             active: 0,
+            shrink_policy: ShrinkPolicy::default(),
         }
     }
 }
@@ -58,6 +126,63 @@ impl<T> ResizingVec<T> {
         Self {
             data: vec,
             active: 0,
+            shrink_policy: ShrinkPolicy::default(),
+        }
+    }
+
+    /// Sets the policy used to reclaim space after
+    /// [`remove()`](#method.remove); see [`ShrinkPolicy`] for which variants
+    /// apply automatically. Defaults to [`ShrinkPolicy::Never`], leaving
+    /// [`resize()`](#method.resize) fully manual.
+    pub fn set_shrink_policy(&mut self, policy: ShrinkPolicy) {
+        self.shrink_policy = policy;
+    }
+
+    fn fill_ratio(&self) -> f64 {
+        if self.data.is_empty() {
+            1.0
+        } else {
+            self.active as f64 / self.data.len() as f64
+        }
+    }
+
+    /// Applies the configured [`ShrinkPolicy`] after a successful removal at
+    /// `removed_idx`, triggering on either of the two conditions the policy
+    /// documents: the highest occupied slot was just vacated, or the fill
+    /// ratio dropped below the configured threshold.
+    ///
+    /// [`ShrinkPolicy::Resize`] is intentionally not handled here: a full
+    /// `resize()` reassigns every surviving index, and doing that silently
+    /// from inside `remove()` would break index stability without any way
+    /// for the caller to observe it. Callers opt into that reindex
+    /// explicitly via [`shrink_if_needed()`](#method.shrink_if_needed).
+    fn maybe_shrink(&mut self, removed_idx: usize) {
+        if let ShrinkPolicy::TruncateTrailing { threshold } = self.shrink_policy {
+            let dropped_highest = self.data[removed_idx + 1..].iter().all(Option::is_none);
+            if dropped_highest || self.fill_ratio() < threshold {
+                while matches!(self.data.last(), Some(None)) {
+                    self.data.pop();
+                }
+            }
+        }
+    }
+
+    /// Explicitly applies [`ShrinkPolicy::Resize`] if the configured
+    /// threshold is currently met, returning the [`Position`]s of any moved
+    /// elements (the same contract as calling [`resize()`](#method.resize)
+    /// directly). Returns `None` without touching `self` for
+    /// [`ShrinkPolicy::Never`]/[`ShrinkPolicy::TruncateTrailing`], or when
+    /// the threshold isn't met.
+    ///
+    /// Unlike `TruncateTrailing`, this is never triggered automatically from
+    /// [`remove()`](#method.remove): reassigning indices is a visible,
+    /// opt-in operation.
+    pub fn shrink_if_needed(&mut self) -> Option<Vec<Position>> {
+        match self.shrink_policy {
+            ShrinkPolicy::Resize { threshold } if self.fill_ratio() < threshold => {
+                Some(self.resize())
+            }
+            _ => None,
         }
     }
 
@@ -103,6 +228,15 @@ impl<T> ResizingVec<T> {
             .filter_map(|(idx, t)| t.as_ref().map(|e| (idx, e)))
     }
 
+    /// Returns an iterator over mutable references to the active elements,
+    /// alongside their index.
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (usize, &mut T)> + '_ {
+        self.data
+            .iter_mut()
+            .enumerate()
+            .filter_map(|(idx, t)| t.as_mut().map(|e| (idx, e)))
+    }
+
     /// Removes the element at the given index and returns the
     /// remove element. If the given index is out of bounds
     /// than None is being returned
@@ -111,6 +245,7 @@ impl<T> ResizingVec<T> {
             let prev = self.data[idx].take();
             if prev.is_some() {
                 self.active -= 1;
+                self.maybe_shrink(idx);
             }
 
             prev
@@ -119,6 +254,40 @@ impl<T> ResizingVec<T> {
         }
     }
 
+    /// Retains only the active elements for which `f` returns `true`,
+    /// removing the rest. The reserved space is left intact whatever the
+    /// configured [`ShrinkPolicy`]; call [`resize()`](#method.resize)
+    /// afterwards to compact it.
+    pub fn retain<F: FnMut(usize, &T) -> bool>(&mut self, mut f: F) {
+        for (idx, slot) in self.data.iter_mut().enumerate() {
+            if let Some(t) = slot {
+                if !f(idx, t) {
+                    *slot = None;
+                    self.active -= 1;
+                }
+            }
+        }
+    }
+
+    /// Removes and returns every active element for which `f` returns
+    /// `false`, as `(usize, T)` pairs. This is the complement of
+    /// [`retain()`](#method.retain) for when the removed elements are
+    /// needed rather than discarded.
+    pub fn drain_filter<F: FnMut(usize, &T) -> bool>(&mut self, mut f: F) -> Vec<(usize, T)> {
+        let mut drained = Vec::new();
+
+        for (idx, slot) in self.data.iter_mut().enumerate() {
+            if let Some(t) = slot {
+                if !f(idx, t) {
+                    drained.push((idx, slot.take().unwrap()));
+                    self.active -= 1;
+                }
+            }
+        }
+
+        drained
+    }
+
     /// Inserts the element at the given index.
     /// IMPORTANT: The time complexity of this operation
     /// depends on whether it has to resize or not.
@@ -131,7 +300,7 @@ impl<T> ResizingVec<T> {
             self.data.push(None);
         }
 
-        let prev = std::mem::replace(&mut self.data[idx], Some(t));
+        let prev = core::mem::replace(&mut self.data[idx], Some(t));
 
         if prev.is_none() {
             self.active += 1;
@@ -145,6 +314,28 @@ impl<T> ResizingVec<T> {
         *self = Self::default();
     }
 
+    /// Gets the given index's corresponding entry in the vector for in-place
+    /// manipulation.
+    ///
+    /// This grows the vector once (if needed) so the returned [`Entry`] never
+    /// has to re-check bounds, collapsing the common "check, then insert or
+    /// mutate" pattern into a single index resolution.
+    pub fn entry(&mut self, idx: usize) -> Entry<'_, T> {
+        while self.data.len() <= idx {
+            self.data.push(None);
+        }
+
+        if self.data[idx].is_some() {
+            Entry::Occupied(self.data[idx].as_mut().unwrap())
+        } else {
+            let Self { data, active, .. } = self;
+            Entry::Vacant(VacantEntry {
+                slot: &mut data[idx],
+                active,
+            })
+        }
+    }
+
     /// Resizes the vector shrinks it so that every reserved space is being occupied by an element.
     ///
     /// # Examples
@@ -168,7 +359,7 @@ impl<T> ResizingVec<T> {
         let vec = Vec::with_capacity(self.active);
         let mut positions = Vec::with_capacity(self.active);
 
-        let prev = std::mem::replace(&mut self.data, vec);
+        let prev = core::mem::replace(&mut self.data, vec);
 
         for (idx, elem) in prev.into_iter().enumerate() {
             if elem.is_some() {
@@ -186,6 +377,92 @@ impl<T> ResizingVec<T> {
     }
 }
 
+impl<T> SparseSlots<T> for ResizingVec<T> {
+    fn get(&self, idx: usize) -> Option<&T> {
+        self.get(idx)
+    }
+
+    fn get_mut(&mut self, idx: usize) -> Option<&mut T> {
+        self.get_mut(idx)
+    }
+
+    fn remove(&mut self, idx: usize) -> Option<T> {
+        self.remove(idx)
+    }
+
+    fn filled(&self) -> usize {
+        self.filled()
+    }
+}
+
+/// A view into a single slot of a [`ResizingVec`], obtained via [`entry()`](ResizingVec::entry).
+pub enum Entry<'a, T> {
+    /// The slot already holds a value.
+    Occupied(&'a mut T),
+    /// The slot is empty.
+    Vacant(VacantEntry<'a, T>),
+}
+
+impl<'a, T> Entry<'a, T> {
+    /// Ensures a value is in the entry by inserting `default` if vacant, and
+    /// returns a mutable reference to the value.
+    pub fn or_insert(self, default: T) -> &'a mut T {
+        match self {
+            Entry::Occupied(t) => t,
+            Entry::Vacant(v) => v.insert(default),
+        }
+    }
+
+    /// Ensures a value is in the entry by inserting the result of `f` if
+    /// vacant, and returns a mutable reference to the value.
+    pub fn or_insert_with<F: FnOnce() -> T>(self, f: F) -> &'a mut T {
+        match self {
+            Entry::Occupied(t) => t,
+            Entry::Vacant(v) => v.insert(f()),
+        }
+    }
+
+    /// Ensures a value is in the entry by inserting `T::default()` if vacant,
+    /// and returns a mutable reference to the value.
+    pub fn or_default(self) -> &'a mut T
+    where
+        T: Default,
+    {
+        match self {
+            Entry::Occupied(t) => t,
+            Entry::Vacant(v) => v.insert(T::default()),
+        }
+    }
+
+    /// Provides in-place mutable access to an occupied value before any
+    /// `or_insert*` call.
+    pub fn and_modify<F: FnOnce(&mut T)>(self, f: F) -> Self {
+        match self {
+            Entry::Occupied(t) => {
+                f(t);
+                Entry::Occupied(t)
+            }
+            Entry::Vacant(v) => Entry::Vacant(v),
+        }
+    }
+}
+
+/// The vacant variant of an [`Entry`].
+pub struct VacantEntry<'a, T> {
+    slot: &'a mut Option<T>,
+    active: &'a mut usize,
+}
+
+impl<'a, T> VacantEntry<'a, T> {
+    /// Sets the value of the entry, bumps the active count, and returns a
+    /// mutable reference to it.
+    pub fn insert(self, t: T) -> &'a mut T {
+        *self.slot = Some(t);
+        *self.active += 1;
+        self.slot.as_mut().unwrap()
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub struct Position {
     /// The previous index of the element before resizing
@@ -201,6 +478,39 @@ impl Position {
     }
 }
 
+/// Controls how a [`ResizingVec`] reclaims space after removals, set via
+/// [`set_shrink_policy()`](ResizingVec::set_shrink_policy).
+///
+/// For `TruncateTrailing`, a shrink is considered on
+/// [`remove()`](ResizingVec::remove) whenever the highest occupied slot is
+/// removed, or whenever `filled() / reserved_space()` drops below
+/// `threshold`. For `Resize`, only the fill ratio is checked, and only when
+/// [`shrink_if_needed()`](ResizingVec::shrink_if_needed) is called.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum ShrinkPolicy {
+    /// Never shrink; [`resize()`](ResizingVec::resize) must be called
+    /// manually. This is the default.
+    #[default]
+    Never,
+    /// Automatically truncate trailing `None` slots on
+    /// [`remove()`](ResizingVec::remove), a cheap, index-preserving
+    /// operation that only ever reclaims space past the highest occupied
+    /// slot.
+    TruncateTrailing {
+        /// The fill ratio below which a shrink is considered.
+        threshold: f64,
+    },
+    /// Make [`shrink_if_needed()`](ResizingVec::shrink_if_needed) trigger a
+    /// full [`resize()`](ResizingVec::resize), compacting every hole and
+    /// reassigning indices. Unlike `TruncateTrailing` this is never applied
+    /// automatically from `remove()`, since it would silently move
+    /// surviving elements.
+    Resize {
+        /// The fill ratio below which a shrink is considered.
+        threshold: f64,
+    },
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -285,4 +595,179 @@ mod tests {
         assert_eq!(rv.reserved_space(), 4);
         assert_eq!(rv.filled(), 4);
     }
+
+    /// Drives any [`SparseSlots`] implementor purely through the trait,
+    /// proving the abstraction actually unifies `ResizingVec` and
+    /// `ArrayResizingVec` rather than just forwarding unused boilerplate.
+    fn exercise_sparse_slots<S: SparseSlots<i32>>(mut slots: S, idx: usize, expected: i32) {
+        assert_eq!(slots.filled(), 1);
+        assert_eq!(slots.get(idx), Some(&expected));
+
+        *slots.get_mut(idx).unwrap() += 1;
+        assert_eq!(slots.get(idx), Some(&(expected + 1)));
+
+        assert_eq!(slots.remove(idx), Some(expected + 1));
+        assert_eq!(slots.filled(), 0);
+    }
+
+    #[test]
+    fn sparse_slots_trait_is_polymorphic() {
+        let mut rv = ResizingVec::new();
+        rv.insert(2, 10);
+        exercise_sparse_slots(rv, 2, 10);
+
+        let mut av: ArrayResizingVec<i32, 4> = ArrayResizingVec::new();
+        av.insert(2, 10).unwrap();
+        exercise_sparse_slots(av, 2, 10);
+    }
+
+    #[test]
+    fn entry() {
+        let mut rv: ResizingVec<Vec<i32>> = ResizingVec::default();
+
+        rv.entry(3).or_default().push(1);
+        rv.entry(3).or_default().push(2);
+
+        assert_eq!(rv.get(3), Some(&vec![1, 2]));
+        assert_eq!(rv.reserved_space(), 4);
+        assert_eq!(rv.filled(), 1);
+
+        rv.entry(0).or_insert_with(|| vec![9]);
+        assert_eq!(rv.get(0), Some(&vec![9]));
+        assert_eq!(rv.filled(), 2);
+
+        rv.entry(0).and_modify(|v| v.push(10)).or_default();
+        assert_eq!(rv.get(0), Some(&vec![9, 10]));
+    }
+
+    #[test]
+    fn iter_mut_into_iter_from_iter_extend() {
+        let mut rv = ResizingVec::default();
+        rv.insert(1, 10);
+        rv.insert(3, 30);
+
+        for (idx, v) in rv.iter_mut() {
+            *v += idx;
+        }
+        assert_eq!(rv.get(1), Some(&11));
+        assert_eq!(rv.get(3), Some(&33));
+
+        rv.extend([(5, 50), (7, 70)]);
+        assert_eq!(rv.get(5), Some(&50));
+        assert_eq!(rv.filled(), 4);
+
+        let collected = rv.into_iter().collect::<Vec<_>>();
+        assert_eq!(collected, vec![(1, 11), (3, 33), (5, 50), (7, 70)]);
+
+        let rebuilt = collected.into_iter().collect::<ResizingVec<_>>();
+        assert_eq!(rebuilt.reserved_space(), 8);
+        assert_eq!(rebuilt.filled(), 4);
+    }
+
+    #[test]
+    fn retain_and_drain_filter() {
+        let mut rv = ResizingVec::default();
+        rv.insert(0, 0);
+        rv.insert(1, 1);
+        rv.insert(2, 2);
+        rv.insert(3, 3);
+
+        rv.retain(|_, v| v % 2 == 0);
+
+        assert_eq!(rv.filled(), 2);
+        assert_eq!(rv.reserved_space(), 4);
+        assert_eq!(rv.get(0), Some(&0));
+        assert_eq!(rv.get(1), None);
+        assert_eq!(rv.get(2), Some(&2));
+        assert_eq!(rv.get(3), None);
+
+        let drained = rv.drain_filter(|idx, _| idx == 2);
+
+        assert_eq!(drained, vec![(0, 0)]);
+        assert_eq!(rv.filled(), 1);
+        assert_eq!(rv.get(2), Some(&2));
+    }
+
+    #[test]
+    fn shrink_policy_truncate_trailing() {
+        let mut rv = ResizingVec::default();
+        rv.set_shrink_policy(ShrinkPolicy::TruncateTrailing { threshold: 0.5 });
+
+        rv.insert(0, "0");
+        rv.insert(4, "4");
+        assert_eq!(rv.reserved_space(), 5);
+
+        rv.remove(4);
+
+        assert_eq!(rv.reserved_space(), 1);
+        assert_eq!(rv.filled(), 1);
+        assert_eq!(rv.get(0), Some(&"0"));
+    }
+
+    #[test]
+    fn shrink_policy_resize_is_never_automatic() {
+        let mut rv = ResizingVec::default();
+        rv.set_shrink_policy(ShrinkPolicy::Resize { threshold: 1.0 });
+
+        rv.insert(0, "a");
+        rv.insert(5, "b");
+        rv.insert(9, "c");
+
+        rv.remove(9);
+
+        // `remove()` must never silently reindex: "b" stays put until
+        // `shrink_if_needed()` is called explicitly.
+        assert_eq!(rv.reserved_space(), 10);
+        assert_eq!(rv.get(5), Some(&"b"));
+
+        let positions = rv.shrink_if_needed().expect("threshold was met");
+
+        assert_eq!(rv.reserved_space(), 2);
+        assert_eq!(rv.filled(), 2);
+        assert_eq!(rv.get(5), None);
+        assert_eq!(rv.get(1), Some(&"b"));
+        assert!(positions.iter().any(|p| p.changed()));
+
+        assert_eq!(rv.shrink_if_needed(), None);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        let mut rv = ResizingVec::default();
+        rv.insert(2, "2");
+        rv.insert(9, "9");
+
+        let json = serde_json::to_string(&rv).unwrap();
+        assert_eq!(json, r#"[[2,"2"],[9,"9"]]"#);
+
+        let round_tripped: ResizingVec<&str> = serde_json::from_str(&json).unwrap();
+        assert_eq!(round_tripped.reserved_space(), 10);
+        assert_eq!(round_tripped.filled(), 2);
+        assert_eq!(round_tripped.get(2), Some(&"2"));
+        assert_eq!(round_tripped.get(9), Some(&"9"));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_rejects_duplicate_index() {
+        let err = serde_json::from_str::<ResizingVec<&str>>(r#"[[2,"a"],[2,"b"]]"#).unwrap_err();
+        assert!(err.to_string().contains("duplicate index"));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_rejects_overflowing_index() {
+        let err = serde_json::from_str::<ResizingVec<&str>>(r#"[[18446744073709551615,"a"]]"#)
+            .unwrap_err();
+        assert!(err.to_string().contains("overflows"));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_rejects_excessively_sparse_index() {
+        let err = serde_json::from_str::<ResizingVec<&str>>(r#"[[9223372036854775807,"a"]]"#)
+            .unwrap_err();
+        assert!(err.to_string().contains("sparsity limit"));
+    }
 }